@@ -0,0 +1,128 @@
+use crate::error::Converter;
+use crate::Error;
+use crate::RecordDataType;
+use crate::RecordValue;
+use crate::Result;
+
+/// A single contiguous, typed column of decoded values for one prototype field.
+///
+/// Keeping values column-oriented avoids the per-point `Vec<RecordValue>` and
+/// the enum boxing of the row iterator, so whole columns can be handed to
+/// downstream SIMD or GPU pipelines.
+#[derive(Clone, Debug)]
+pub enum Column {
+    Single(Vec<f32>),
+    Double(Vec<f64>),
+    ScaledInteger(Vec<i64>),
+    Integer(Vec<i64>),
+}
+
+impl Column {
+    /// Creates an empty column matching the storage of the given record type.
+    pub fn new(data_type: &RecordDataType) -> Self {
+        match data_type {
+            RecordDataType::Single { .. } => Column::Single(Vec::new()),
+            RecordDataType::Double { .. } => Column::Double(Vec::new()),
+            RecordDataType::ScaledInteger { .. } => Column::ScaledInteger(Vec::new()),
+            RecordDataType::Integer { .. } => Column::Integer(Vec::new()),
+        }
+    }
+
+    /// Number of values currently stored in the column.
+    pub fn len(&self) -> usize {
+        match self {
+            Column::Single(v) => v.len(),
+            Column::Double(v) => v.len(),
+            Column::ScaledInteger(v) => v.len(),
+            Column::Integer(v) => v.len(),
+        }
+    }
+
+    /// Returns `true` if the column contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends a decoded value, failing if it does not match the column type.
+    pub fn push(&mut self, value: RecordValue) -> Result<()> {
+        match (self, value) {
+            (Column::Single(v), RecordValue::Single(x)) => v.push(x),
+            (Column::Double(v), RecordValue::Double(x)) => v.push(x),
+            (Column::ScaledInteger(v), RecordValue::ScaledInteger(x)) => v.push(x),
+            (Column::Integer(v), RecordValue::Integer(x)) => v.push(x),
+            _ => Error::invalid("Decoded value does not match column type")?,
+        }
+        Ok(())
+    }
+
+    /// Returns the value at `index` as a [`RecordValue`] for row-oriented access.
+    pub fn get(&self, index: usize) -> Option<RecordValue> {
+        match self {
+            Column::Single(v) => v.get(index).map(|x| RecordValue::Single(*x)),
+            Column::Double(v) => v.get(index).map(|x| RecordValue::Double(*x)),
+            Column::ScaledInteger(v) => v.get(index).map(|x| RecordValue::ScaledInteger(*x)),
+            Column::Integer(v) => v.get(index).map(|x| RecordValue::Integer(*x)),
+        }
+    }
+}
+
+/// A batch of decoded records stored as one typed column per prototype field.
+///
+/// This is the column-oriented counterpart of a slice of `RawValues`, in the
+/// spirit of an Arrow record batch: every column holds the same number of
+/// values and column `i` corresponds to prototype field `i`.
+#[derive(Clone, Debug)]
+pub struct Columns {
+    columns: Vec<Column>,
+    len: usize,
+}
+
+impl Columns {
+    /// Creates an empty batch with one column per prototype field.
+    pub fn new(prototype: &[crate::Record]) -> Self {
+        Self {
+            columns: prototype.iter().map(|r| Column::new(&r.data_type)).collect(),
+            len: 0,
+        }
+    }
+
+    /// Number of records (rows) contained in the batch.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the batch holds no records.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The typed columns of the batch, ordered like the prototype.
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// Appends one value to column `field`.
+    pub(crate) fn push_value(&mut self, field: usize, value: RecordValue) -> Result<()> {
+        self.columns
+            .get_mut(field)
+            .internal_err("Column index out of range")?
+            .push(value)
+    }
+
+    /// Marks that a full record has been appended across all columns.
+    pub(crate) fn commit_record(&mut self) {
+        self.len += 1;
+    }
+
+    /// Extracts record `index` as a row of [`RecordValue`]s, for the row adapter.
+    pub(crate) fn row(&self, index: usize) -> Option<Vec<RecordValue>> {
+        if index >= self.len {
+            return None;
+        }
+        let mut row = Vec::with_capacity(self.columns.len());
+        for column in &self.columns {
+            row.push(column.get(index)?);
+        }
+        Some(row)
+    }
+}
@@ -0,0 +1,80 @@
+use crate::RecordValue;
+use digest::Digest;
+
+/// Content digest algorithm used for point cloud integrity hashing.
+///
+/// Exposed so users can match the checksums already stored by their
+/// asset-management systems, analogous to nodtool's `--md5` switch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    /// MD5, 16 byte digest.
+    Md5,
+    /// SHA-256, 32 byte digest.
+    Sha256,
+}
+
+enum Inner {
+    Md5(md5::Md5),
+    Sha256(sha2::Sha256),
+}
+
+/// Incremental hasher over the canonical value sequence of a point cloud.
+///
+/// Decoded values are fed in prototype order, record by record, so the
+/// resulting digest is stable regardless of the section's packet layout. The
+/// same hasher can also absorb raw section bytes via [`RecordHasher::update_bytes`].
+pub struct RecordHasher {
+    inner: Inner,
+}
+
+impl RecordHasher {
+    /// Creates a hasher for the given algorithm.
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        let inner = match algorithm {
+            HashAlgorithm::Md5 => Inner::Md5(md5::Md5::new()),
+            HashAlgorithm::Sha256 => Inner::Sha256(sha2::Sha256::new()),
+        };
+        Self { inner }
+    }
+
+    /// Feeds the canonical little-endian encoding of a decoded value.
+    ///
+    /// A one byte type tag is mixed in first so that values of different types
+    /// but identical bit patterns cannot collide.
+    pub fn update_value(&mut self, value: &RecordValue) {
+        match value {
+            RecordValue::Single(x) => {
+                self.update_bytes(&[0]);
+                self.update_bytes(&x.to_le_bytes());
+            }
+            RecordValue::Double(x) => {
+                self.update_bytes(&[1]);
+                self.update_bytes(&x.to_le_bytes());
+            }
+            RecordValue::ScaledInteger(x) => {
+                self.update_bytes(&[2]);
+                self.update_bytes(&x.to_le_bytes());
+            }
+            RecordValue::Integer(x) => {
+                self.update_bytes(&[3]);
+                self.update_bytes(&x.to_le_bytes());
+            }
+        }
+    }
+
+    /// Feeds raw bytes, e.g. the binary section bytes, into the hasher.
+    pub fn update_bytes(&mut self, bytes: &[u8]) {
+        match &mut self.inner {
+            Inner::Md5(h) => h.update(bytes),
+            Inner::Sha256(h) => h.update(bytes),
+        }
+    }
+
+    /// Consumes the hasher and returns the final digest bytes.
+    pub fn finalize(self) -> Vec<u8> {
+        match self.inner {
+            Inner::Md5(h) => h.finalize().to_vec(),
+            Inner::Sha256(h) => h.finalize().to_vec(),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use crate::error::Converter;
+use crate::packet::DataPacketHeader;
+use crate::packet::IndexPacketHeader;
+use crate::packet::PacketHeader;
+use crate::paged_reader::PagedReader;
+use crate::Result;
+use std::io::{Read, Seek};
+
+/// Single entry of a parsed `Index` packet.
+///
+/// Each entry points at the physical start of a data packet and records the
+/// decoded record number of its first point, which allows seeking without
+/// decoding all preceding packets.
+#[derive(Clone, Debug)]
+pub struct IndexEntry {
+    /// Record number of the first point inside the referenced data packet.
+    pub chunk_record_number: u64,
+    /// Physical file offset of the referenced data packet.
+    pub chunk_physical_offset: u64,
+}
+
+/// Fully parsed `Index` packet with its header and entry list.
+#[derive(Clone, Debug)]
+pub struct IndexPacket {
+    pub header: IndexPacketHeader,
+    pub entries: Vec<IndexEntry>,
+}
+
+/// Number of header bytes of an ignored packet consumed before its payload:
+/// the 1-byte packet type plus the reserved byte and 2-byte logical length.
+const IGNORED_HEADER_SIZE: usize = 4;
+
+/// A single typed packet yielded by the [`PacketReader`].
+///
+/// `Data` only carries its header: the payload (buffer sizes and byte stream
+/// bytes) stays in the underlying reader so the caller can stream it directly
+/// into the byte stream buffers without an intermediate copy.
+pub enum Packet {
+    /// A data packet header; its payload is still pending in the reader.
+    Data(DataPacketHeader),
+    /// A fully parsed index packet.
+    Index(IndexPacket),
+    /// An ignored packet that was consumed and skipped transparently.
+    Ignored,
+}
+
+/// Reads the packet stream of a CompressedVector section on top of a [`PagedReader`].
+///
+/// This turns the raw sequence of `Index`, `Data` and `Ignored` packets into a
+/// typed iterator: `Ignored` packets are skipped transparently and `Index`
+/// packets are parsed into their entry list, so callers only ever deal with the
+/// packets they care about.
+pub struct PacketReader<'a, T: Read + Seek> {
+    reader: &'a mut PagedReader<T>,
+}
+
+impl<'a, T: Read + Seek> PacketReader<'a, T> {
+    pub fn new(reader: &'a mut PagedReader<T>) -> Self {
+        Self { reader }
+    }
+
+    /// Reads and returns the next packet from the stream.
+    ///
+    /// For `Data` packets the caller is expected to read the payload afterwards
+    /// and then call [`PacketReader::align`]. `Index` and `Ignored` packets are
+    /// fully consumed and already aligned on return.
+    pub fn next_packet(&mut self) -> Result<Packet> {
+        let packet_header = PacketHeader::read(self.reader)?;
+        match packet_header {
+            PacketHeader::Data(header) => Ok(Packet::Data(header)),
+            PacketHeader::Index(header) => {
+                let entries = self.read_index_entries(&header)?;
+                self.align()?;
+                Ok(Packet::Index(IndexPacket { header, entries }))
+            }
+            PacketHeader::Ignored(header) => {
+                // `packet_length` is the logical length of the whole packet,
+                // including the 4-byte header already consumed by
+                // `PacketHeader::read` (1 type byte) and `IgnoredPacketHeader::read`
+                // (reserved byte plus the 2-byte length). Skipping the full
+                // length here would over-read by those 4 bytes and, since the
+                // header is a multiple of 4, `align()` could not recover the
+                // stream, so only the remaining payload is skipped.
+                let mut remaining = (header.packet_length as usize).saturating_sub(IGNORED_HEADER_SIZE);
+                let mut skip = [0_u8; 256];
+                while remaining > 0 {
+                    let chunk = remaining.min(skip.len());
+                    self.reader
+                        .read_exact(&mut skip[..chunk])
+                        .read_err("Failed to skip over ignored packet payload")?;
+                    remaining -= chunk;
+                }
+                self.align()?;
+                Ok(Packet::Ignored)
+            }
+        }
+    }
+
+    /// Aligns the underlying reader on the next 4-byte offset after a packet.
+    pub fn align(&mut self) -> Result<()> {
+        self.reader
+            .align()
+            .read_err("Failed to align reader on next 4-byte offset after reading packet")
+    }
+
+    fn read_index_entries(&mut self, header: &IndexPacketHeader) -> Result<Vec<IndexEntry>> {
+        let mut entries = Vec::with_capacity(header.entry_count as usize);
+        for _ in 0..header.entry_count {
+            let mut buffer = [0_u8; 16];
+            self.reader
+                .read_exact(&mut buffer)
+                .read_err("Failed to read index packet entry")?;
+            let chunk_record_number = u64::from_le_bytes(buffer[0..8].try_into().internal_err(
+                "Failed to extract record number from index packet entry",
+            )?);
+            let chunk_physical_offset = u64::from_le_bytes(buffer[8..16].try_into().internal_err(
+                "Failed to extract physical offset from index packet entry",
+            )?);
+            entries.push(IndexEntry {
+                chunk_record_number,
+                chunk_physical_offset,
+            });
+        }
+        Ok(entries)
+    }
+}
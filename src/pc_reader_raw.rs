@@ -1,8 +1,15 @@
 use crate::bitpack::BitPack;
 use crate::bs_read::ByteStreamReadBuffer;
+use crate::columns::Columns;
 use crate::cv_section::CompressedVectorSectionHeader;
 use crate::error::Converter;
-use crate::packet::PacketHeader;
+#[cfg(feature = "hashing")]
+use crate::hashing::HashAlgorithm;
+#[cfg(feature = "hashing")]
+use crate::hashing::RecordHasher;
+use crate::packet::DataPacketHeader;
+use crate::packet_reader::Packet;
+use crate::packet_reader::PacketReader;
 use crate::paged_reader::PagedReader;
 use crate::Error;
 use crate::PointCloud;
@@ -13,17 +20,43 @@ use crate::Result;
 use std::collections::VecDeque;
 use std::io::{Read, Seek};
 
+/// Physical location of a data packet and the record number of its first point.
+///
+/// Used to build a sparse index over the section so callers can seek to an
+/// arbitrary record without decoding every preceding packet.
+#[derive(Clone, Copy, Debug)]
+struct PacketOffset {
+    /// Physical file offset of the data packet header.
+    physical_offset: u64,
+    /// Record number of the first point contained in the packet.
+    start_record: u64,
+}
+
 /// Iterate over all raw points of a point cloud for reading.
 pub struct PointCloudReaderRaw<'a, T: Read + Seek> {
     pc: PointCloud,
     reader: &'a mut PagedReader<T>,
+    /// Physical offset of the first packet of the section.
+    data_offset: u64,
     byte_streams: Vec<ByteStreamReadBuffer>,
     read: u64,
     queues: Vec<VecDeque<RecordValue>>,
     buffer_sizes: Vec<usize>,
     buffer: Vec<u8>,
+    /// Lazily built, cached index of data packet boundaries for random access.
+    packet_index: Option<Vec<PacketOffset>>,
+    /// Current batch buffered for the row iterator adapter.
+    batch: Option<Columns>,
+    /// Offset of the next row to yield from `batch`.
+    batch_pos: usize,
+    /// Optional content hasher fed by every decoded value during reading.
+    #[cfg(feature = "hashing")]
+    hasher: Option<RecordHasher>,
 }
 
+/// Number of records the row iterator decodes per batch refill.
+const ROW_ADAPTER_BATCH: usize = 1024;
+
 impl<'a, T: Read + Seek> PointCloudReaderRaw<'a, T> {
     pub(crate) fn new(pc: &PointCloud, reader: &'a mut PagedReader<T>) -> Result<Self> {
         reader
@@ -37,14 +70,300 @@ impl<'a, T: Read + Seek> PointCloudReaderRaw<'a, T> {
         Ok(Self {
             pc: pc.clone(),
             reader,
+            data_offset: section_header.data_offset,
             read: 0,
             byte_streams: vec![ByteStreamReadBuffer::new(); pc.prototype.len()],
             queues: vec![VecDeque::new(); pc.prototype.len()],
             buffer_sizes: vec![0; pc.prototype.len()],
             buffer: Vec::new(),
+            packet_index: None,
+            batch: None,
+            batch_pos: 0,
+            #[cfg(feature = "hashing")]
+            hasher: None,
         })
     }
 
+    /// Enables content hashing with the given algorithm.
+    ///
+    /// Once enabled, every value is fed into the digest as its record is decoded
+    /// into a batch by [`PointCloudReaderRaw::next_batch`], so a stable content
+    /// hash can be produced alongside a normal read without a second pass.
+    /// Records skipped over by [`PointCloudReaderRaw::seek_record`] (and the
+    /// ones decoded while building the seek index) are not hashed. The row
+    /// iterator decodes whole batches of up to 1024 records, so rows of the
+    /// current batch are hashed even if they are never yielded, for example
+    /// when reading stops early or a seek drops the batch. Call
+    /// [`PointCloudReaderRaw::finish_hash`] after reading all records to obtain
+    /// the digest.
+    #[cfg(feature = "hashing")]
+    pub fn enable_hashing(&mut self, algorithm: HashAlgorithm) {
+        self.hasher = Some(RecordHasher::new(algorithm));
+    }
+
+    /// Consumes the active hasher and returns the digest over the values decoded
+    /// into batches so far, or `None` if hashing was never enabled.
+    #[cfg(feature = "hashing")]
+    pub fn finish_hash(&mut self) -> Option<Vec<u8>> {
+        self.hasher.take().map(|h| h.finalize())
+    }
+
+    /// Hashes the raw binary bytes of the point cloud's CompressedVector section.
+    ///
+    /// This is a convenience digest over the section's packet bytes rather than
+    /// the decoded values, useful for deduplicating identical sections or
+    /// matching a stored checksum without decoding. It covers the section length
+    /// minus the section header, read from the data offset through the paged
+    /// reader, so the section header and page checksums are not included.
+    #[cfg(feature = "hashing")]
+    pub fn hash_raw_section(
+        pc: &PointCloud,
+        reader: &mut PagedReader<T>,
+        algorithm: HashAlgorithm,
+    ) -> Result<Vec<u8>> {
+        reader
+            .seek_physical(pc.file_offset)
+            .read_err("Cannot seek to compressed vector header")?;
+        let section_header = CompressedVectorSectionHeader::read(reader)?;
+        reader
+            .seek_physical(section_header.data_offset)
+            .read_err("Cannot seek to packet data")?;
+
+        let mut hasher = RecordHasher::new(algorithm);
+        let mut remaining = section_header
+            .section_length
+            .saturating_sub(CompressedVectorSectionHeader::SIZE) as usize;
+        let mut buffer = [0_u8; 4096];
+        while remaining > 0 {
+            let chunk = remaining.min(buffer.len());
+            reader
+                .read_exact(&mut buffer[..chunk])
+                .read_err("Failed to read raw section bytes")?;
+            hasher.update_bytes(&buffer[..chunk]);
+            remaining -= chunk;
+        }
+        Ok(hasher.finalize())
+    }
+
+    /// Reads the next batch of up to `max` records as contiguous typed columns.
+    ///
+    /// Returns `None` once the end of the point cloud is reached. Unlike the row
+    /// iterator this avoids the per-point `RawValues` vector and the enum boxing
+    /// of every value, appending `K` values per column for a batch of `K`
+    /// records so whole columns can be handed to vectorized pipelines.
+    pub fn next_batch(&mut self, max: usize) -> Result<Option<Columns>> {
+        if max == 0 || self.read >= self.pc.records {
+            return Ok(None);
+        }
+
+        let remaining = (self.pc.records - self.read) as usize;
+        let target = max.min(remaining);
+        let mut columns = Columns::new(&self.pc.prototype);
+        while columns.len() < target {
+            if self.available_in_queue() < 1 {
+                self.advance()?;
+                if self.available_in_queue() < 1 {
+                    break;
+                }
+            }
+
+            let take = (target - columns.len()).min(self.available_in_queue());
+            for _ in 0..take {
+                for i in 0..self.pc.prototype.len() {
+                    let value = self.queues[i]
+                        .pop_front()
+                        .internal_err("Failed to pop value for next batch")?;
+                    #[cfg(feature = "hashing")]
+                    if let Some(hasher) = &mut self.hasher {
+                        hasher.update_value(&value);
+                    }
+                    columns.push_value(i, value)?;
+                }
+                columns.commit_record();
+                self.read += 1;
+            }
+        }
+
+        if columns.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(columns))
+        }
+    }
+
+    /// Seeks to the point with the given record `index`.
+    ///
+    /// Subsequent reads continue from that record. The first call builds and
+    /// caches a sparse index of packet boundaries, so later seeks are `O(log n)`
+    /// in the number of data packets plus the cost of decoding at most one
+    /// packet's worth of leftover records.
+    pub fn seek_record(&mut self, index: u64) -> Result<()> {
+        if index > self.pc.records {
+            Error::invalid("Cannot seek beyond the number of records")?
+        }
+
+        if self.packet_index.is_none() {
+            let built = self.build_packet_index()?;
+            self.packet_index = Some(built);
+        }
+        let packet_index = self
+            .packet_index
+            .as_ref()
+            .internal_err("Packet index was not built")?;
+
+        // Find the last packet whose first record is <= the target index.
+        let slot = match packet_index.binary_search_by(|p| p.start_record.cmp(&index)) {
+            Ok(slot) => slot,
+            Err(0) => 0,
+            Err(slot) => slot - 1,
+        };
+        let packet = packet_index[slot];
+
+        // Reset all decode state: the bit state of every ByteStreamReadBuffer
+        // must start fresh at a packet boundary, so seeks are only valid at
+        // packet starts plus the in-packet discard below.
+        for bs in &mut self.byte_streams {
+            *bs = ByteStreamReadBuffer::new();
+        }
+        for q in &mut self.queues {
+            q.clear();
+        }
+        for bs in &mut self.buffer_sizes {
+            *bs = 0;
+        }
+        self.batch = None;
+        self.batch_pos = 0;
+        self.reader
+            .seek_physical(packet.physical_offset)
+            .read_err("Cannot seek to data packet")?;
+
+        // Decode and discard the leftover records between the packet boundary
+        // and the target index by popping them straight out of the queues.
+        // Going through the row iterator here would be wrong: `next` serves a
+        // row out of a bulk-decoded batch and so advances the logical position
+        // by a whole batch per call, landing past the target. Discarding at the
+        // record granularity also keeps these skipped records out of the
+        // content hasher, which only sees values popped by `next_batch`.
+        self.discard_records(index - packet.start_record)?;
+        self.read = index;
+
+        Ok(())
+    }
+
+    /// Pops and discards `count` decoded records from the queues, decoding
+    /// further packets as needed, without yielding or hashing them.
+    fn discard_records(&mut self, mut count: u64) -> Result<()> {
+        while count > 0 {
+            if self.available_in_queue() < 1 {
+                self.advance()?;
+                if self.available_in_queue() < 1 {
+                    Error::invalid("Unexpected end of section while seeking")?
+                }
+            }
+            let take = (self.available_in_queue() as u64).min(count);
+            for _ in 0..take {
+                for q in &mut self.queues {
+                    q.pop_front();
+                }
+            }
+            count -= take;
+        }
+        Ok(())
+    }
+
+    /// Walks the section once to record the physical offset and first record
+    /// number of every data packet, using the first parsed level-0 index packet
+    /// to skip decoding when one is present. Higher-level index packets are
+    /// skipped.
+    fn build_packet_index(&mut self) -> Result<Vec<PacketOffset>> {
+        self.reader
+            .seek_physical(self.data_offset)
+            .read_err("Cannot seek to first packet while building index")?;
+
+        // The walk decodes from the section start, so any decode state left
+        // over from points already read must be cleared first. Otherwise the
+        // first packet's records would be counted on top of stale leftovers and
+        // every cumulative `start_record` in the index would be shifted.
+        for bs in &mut self.byte_streams {
+            *bs = ByteStreamReadBuffer::new();
+        }
+        for q in &mut self.queues {
+            q.clear();
+        }
+        for bs in &mut self.buffer_sizes {
+            *bs = 0;
+        }
+
+        let mut index = Vec::new();
+        let mut cumulative = 0_u64;
+        while cumulative < self.pc.records {
+            let offset = self
+                .reader
+                .physical_position()
+                .read_err("Cannot read physical position while building index")?;
+            match PacketReader::new(self.reader).next_packet()? {
+                Packet::Ignored => continue,
+                Packet::Index(packet) => {
+                    // A level-0 index enumerates every data packet boundary of
+                    // the section directly, so we can build the whole index
+                    // from its entries and skip decoding the data packets
+                    // entirely. Higher-level indices point at other index
+                    // packets rather than data packets, so their entries are
+                    // not usable as data boundaries: skip them and fall back to
+                    // counting the data packets.
+                    if packet.header.index_level != 0 {
+                        continue;
+                    }
+                    let mut from_index: Vec<PacketOffset> = packet
+                        .entries
+                        .iter()
+                        .map(|entry| PacketOffset {
+                            physical_offset: entry.chunk_physical_offset,
+                            start_record: entry.chunk_record_number,
+                        })
+                        .collect();
+                    from_index.sort_by_key(|p| p.start_record);
+                    from_index.dedup_by_key(|p| p.start_record);
+                    return Ok(from_index);
+                }
+                Packet::Data(header) => {
+                    index.push(PacketOffset {
+                        physical_offset: offset,
+                        start_record: cumulative,
+                    });
+                    cumulative += self.count_data_packet(&header)?;
+                }
+            }
+        }
+
+        // Data packets are recorded in stream order, but a packet that
+        // completes no record shares its `start_record` with the next one, so
+        // normalize before handing it to the binary search.
+        index.sort_by_key(|p| p.start_record);
+        index.dedup_by_key(|p| p.start_record);
+        Ok(index)
+    }
+
+    /// Decodes a single data packet and returns the number of records that
+    /// became complete across all fields, consuming them from the queues.
+    ///
+    /// Unlike a throwaway decode this preserves the byte-stream bit carryover
+    /// and the per-field leftover values across packet boundaries, exactly like
+    /// the sequential read path. Resetting the byte streams or clearing the
+    /// queues here would desync bit-packed `Integer`/`ScaledInteger` fields
+    /// whose records straddle a packet boundary, so the counted record
+    /// boundaries would drift away from a real read and seeks would miss.
+    fn count_data_packet(&mut self, header: &DataPacketHeader) -> Result<u64> {
+        self.read_data_packet(header)?;
+        let count = self.available_in_queue() as u64;
+        for _ in 0..count {
+            for q in &mut self.queues {
+                q.pop_front();
+            }
+        }
+        Ok(count)
+    }
+
     fn available_in_queue(&self) -> usize {
         if self.queues.is_empty() {
             return 0;
@@ -60,74 +379,48 @@ impl<'a, T: Read + Seek> PointCloudReaderRaw<'a, T> {
         av
     }
 
-    fn pop_queue_point(&mut self) -> Result<RawValues> {
-        let mut point = RawValues::with_capacity(self.pc.prototype.len());
-        for i in 0..self.pc.prototype.len() {
-            let value = self.queues[i]
-                .pop_front()
-                .internal_err("Failed to pop value for next point")?;
-            point.push(value);
+    fn advance(&mut self) -> Result<()> {
+        // Drain the packet stream until we reach a data packet that refills the
+        // queues. Index and ignored packets are handled transparently by the
+        // packet reader, so they never stall the decode loop.
+        loop {
+            let packet = PacketReader::new(self.reader).next_packet()?;
+            match packet {
+                // Index and ignored packets carry no point data: keep reading.
+                Packet::Index(_) | Packet::Ignored => continue,
+                Packet::Data(header) => {
+                    self.read_data_packet(&header)?;
+                    return Ok(());
+                }
+            }
         }
-        Ok(point)
     }
 
-    fn advance(&mut self) -> Result<()> {
-        let packet_header = PacketHeader::read(self.reader)?;
-        match packet_header {
-            PacketHeader::Index(_) => {
-                Error::not_implemented("Index packets are not yet supported")?
-            }
-            PacketHeader::Ignored(_) => {
-                Error::not_implemented("Ignored packets are not yet supported")?
-            }
-            PacketHeader::Data(header) => {
-                if header.bytestream_count as usize != self.byte_streams.len() {
-                    Error::invalid("Bytestream count does not match prototype size")?
-                }
+    /// Reads the payload of a data packet, appends it to the byte streams,
+    /// unpacks each prototype field into its queue and aligns the reader.
+    fn read_data_packet(&mut self, header: &DataPacketHeader) -> Result<()> {
+        if header.bytestream_count as usize != self.byte_streams.len() {
+            Error::invalid("Bytestream count does not match prototype size")?
+        }
 
-                for i in 0..header.bytestream_count as usize {
-                    let mut buf = [0_u8; 2];
-                    self.reader
-                        .read_exact(&mut buf)
-                        .read_err("Failed to read data packet buffer sizes")?;
-                    let len = u16::from_le_bytes(buf) as usize;
-                    self.buffer_sizes[i] = len;
-                }
+        for i in 0..header.bytestream_count as usize {
+            let mut buf = [0_u8; 2];
+            self.reader
+                .read_exact(&mut buf)
+                .read_err("Failed to read data packet buffer sizes")?;
+            let len = u16::from_le_bytes(buf) as usize;
+            self.buffer_sizes[i] = len;
+        }
 
-                for (i, bs) in self.buffer_sizes.iter().enumerate() {
-                    self.buffer.resize(*bs, 0_u8);
-                    self.reader
-                        .read_exact(&mut self.buffer)
-                        .read_err("Failed to read data packet buffers")?;
-                    self.byte_streams[i].append(&self.buffer);
-                }
+        for (i, bs) in self.buffer_sizes.iter().enumerate() {
+            self.buffer.resize(*bs, 0_u8);
+            self.reader
+                .read_exact(&mut self.buffer)
+                .read_err("Failed to read data packet buffers")?;
+            self.byte_streams[i].append(&self.buffer);
+        }
 
-                for (i, r) in self.pc.prototype.iter().enumerate() {
-                    match r.data_type {
-                        RecordDataType::Single { .. } => {
-                            BitPack::unpack_singles(&mut self.byte_streams[i], &mut self.queues[i])?
-                        }
-                        RecordDataType::Double { .. } => {
-                            BitPack::unpack_doubles(&mut self.byte_streams[i], &mut self.queues[i])?
-                        }
-                        RecordDataType::ScaledInteger { min, max, .. } => {
-                            BitPack::unpack_scaled_ints(
-                                &mut self.byte_streams[i],
-                                min,
-                                max,
-                                &mut self.queues[i],
-                            )?
-                        }
-                        RecordDataType::Integer { min, max } => BitPack::unpack_ints(
-                            &mut self.byte_streams[i],
-                            min,
-                            max,
-                            &mut self.queues[i],
-                        )?,
-                    };
-                }
-            }
-        };
+        self.unpack_fields()?;
 
         self.reader
             .align()
@@ -135,6 +428,52 @@ impl<'a, T: Read + Seek> PointCloudReaderRaw<'a, T> {
 
         Ok(())
     }
+
+    /// Unpacks every prototype field's byte stream into its queue.
+    ///
+    /// Each field owns an independent `ByteStreamReadBuffer` and output queue,
+    /// so the unpack calls have no data dependency. Without the `rayon` feature
+    /// they run sequentially.
+    #[cfg(not(feature = "rayon"))]
+    fn unpack_fields(&mut self) -> Result<()> {
+        for (i, r) in self.pc.prototype.iter().enumerate() {
+            unpack_field(&mut self.byte_streams[i], &r.data_type, &mut self.queues[i])?;
+        }
+        Ok(())
+    }
+
+    /// Parallel variant of [`PointCloudReaderRaw::unpack_fields`].
+    ///
+    /// Splits the `(byte_stream, prototype, queue)` tuples across the rayon
+    /// thread pool and joins before the caller pops the next point, turning the
+    /// per-packet decode into a parallel map for clouds with many fields.
+    #[cfg(feature = "rayon")]
+    fn unpack_fields(&mut self) -> Result<()> {
+        use rayon::prelude::*;
+        let prototype = &self.pc.prototype;
+        self.byte_streams
+            .par_iter_mut()
+            .zip(self.queues.par_iter_mut())
+            .zip(prototype.par_iter())
+            .try_for_each(|((bs, queue), r)| unpack_field(bs, &r.data_type, queue))
+    }
+}
+
+/// Unpacks a single field's byte stream into its output queue.
+fn unpack_field(
+    bs: &mut ByteStreamReadBuffer,
+    data_type: &RecordDataType,
+    queue: &mut VecDeque<RecordValue>,
+) -> Result<()> {
+    match data_type {
+        RecordDataType::Single { .. } => BitPack::unpack_singles(bs, queue)?,
+        RecordDataType::Double { .. } => BitPack::unpack_doubles(bs, queue)?,
+        RecordDataType::ScaledInteger { min, max, .. } => {
+            BitPack::unpack_scaled_ints(bs, *min, *max, queue)?
+        }
+        RecordDataType::Integer { min, max } => BitPack::unpack_ints(bs, *min, *max, queue)?,
+    };
+    Ok(())
 }
 
 impl<'a, T: Read + Seek> Iterator for PointCloudReaderRaw<'a, T> {
@@ -142,37 +481,44 @@ impl<'a, T: Read + Seek> Iterator for PointCloudReaderRaw<'a, T> {
     type Item = Result<RawValues>;
 
     /// Returns the next available point or None if the end was reached.
+    ///
+    /// This is a thin adapter over [`PointCloudReaderRaw::next_batch`]: it
+    /// serves rows out of the current batch and decodes a fresh batch whenever
+    /// the buffered one is exhausted.
     fn next(&mut self) -> Option<Self::Item> {
-        // Already read all points?
-        if self.read >= self.pc.records {
-            return None;
-        }
-
-        // Refill property queues if required
-        if self.available_in_queue() < 1 {
-            if let Err(err) = self.advance() {
-                return Some(Err(err));
+        loop {
+            if let Some(batch) = &self.batch {
+                if self.batch_pos < batch.len() {
+                    let row = batch.row(self.batch_pos);
+                    self.batch_pos += 1;
+                    return match row {
+                        Some(row) => Some(Ok(row)),
+                        None => Some(Err(Error::internal("Failed to extract row from batch"))),
+                    };
+                }
             }
-        }
-
-        // Try to read next point from properties queues
-        if self.available_in_queue() < 1 {
-            return None;
-        }
 
-        // Extract next point
-        match self.pop_queue_point() {
-            Ok(point) => {
-                self.read += 1;
-                Some(Ok(point))
+            match self.next_batch(ROW_ADAPTER_BATCH) {
+                Ok(Some(batch)) => {
+                    self.batch = Some(batch);
+                    self.batch_pos = 0;
+                }
+                Ok(None) => return None,
+                Err(err) => return Some(Err(err)),
             }
-            Err(err) => Some(Err(err)),
         }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let overall = self.pc.records;
-        let remaining = overall - self.read;
+        // `read` counts records already decoded out of the queues into batches,
+        // not records yielded, so the rows still buffered in the current batch
+        // but not yet served have to be added back to get the remaining count.
+        let buffered = self
+            .batch
+            .as_ref()
+            .map(|b| b.len() - self.batch_pos)
+            .unwrap_or(0) as u64;
+        let remaining = (self.pc.records - self.read) + buffered;
         (remaining as usize, Some(remaining as usize))
     }
 }
@@ -195,6 +195,117 @@ fn raw_iterator() {
     assert_eq!(counter, pc.records);
 }
 
+#[cfg(feature = "hashing")]
+#[test]
+fn hashing_is_stable_across_seek() {
+    use e57::HashAlgorithm;
+
+    let file = "testdata/bunnyFloat.e57";
+    let mut reader = E57Reader::from_file(file).unwrap();
+    let pcs = reader.pointclouds();
+    let pc = pcs.first().unwrap().clone();
+
+    // Plain linear read with hashing enabled.
+    let mut raw = reader.pointcloud_raw(&pc).unwrap();
+    raw.enable_hashing(HashAlgorithm::Sha256);
+    for p in raw.by_ref() {
+        p.unwrap();
+    }
+    let plain = raw.finish_hash().unwrap();
+
+    // Seeking to the start must not feed any discarded or index-walk records
+    // into the hasher, so a full read afterwards yields the identical digest.
+    let mut raw = reader.pointcloud_raw(&pc).unwrap();
+    raw.enable_hashing(HashAlgorithm::Sha256);
+    raw.seek_record(0).unwrap();
+    for p in raw.by_ref() {
+        p.unwrap();
+    }
+    let seeked = raw.finish_hash().unwrap();
+
+    assert_eq!(plain, seeked);
+}
+
+#[test]
+fn next_batch_matches_row_iterator() {
+    let file = "testdata/tinyCartesianFloatRgb.e57";
+    let mut reader = E57Reader::from_file(file).unwrap();
+    let pcs = reader.pointclouds();
+    let pc = pcs.first().unwrap().clone();
+
+    let rows: Vec<RawValues> = reader
+        .pointcloud_raw(&pc)
+        .unwrap()
+        .map(|p| p.unwrap())
+        .collect();
+
+    let mut batched: Vec<RawValues> = Vec::new();
+    let mut raw = reader.pointcloud_raw(&pc).unwrap();
+    while let Some(columns) = raw.next_batch(256).unwrap() {
+        for r in 0..columns.len() {
+            let row: RawValues = columns
+                .columns()
+                .iter()
+                .map(|c| c.get(r).unwrap())
+                .collect();
+            batched.push(row);
+        }
+    }
+
+    assert_eq!(batched.len() as u64, pc.records);
+    assert_eq!(batched, rows);
+}
+
+#[test]
+fn seek_record() {
+    let file = "testdata/bunnyFloat.e57";
+    let mut reader = E57Reader::from_file(file).unwrap();
+    let pcs = reader.pointclouds();
+    let pc = pcs.first().unwrap().clone();
+    let all: Vec<RawValues> = reader
+        .pointcloud_raw(&pc)
+        .unwrap()
+        .map(|p| p.unwrap())
+        .collect();
+
+    let mut raw = reader.pointcloud_raw(&pc).unwrap();
+    for index in [0_u64, 1, 1234, 20000, pc.records - 1] {
+        raw.seek_record(index).unwrap();
+        let point = raw.next().unwrap().unwrap();
+        assert_eq!(point, all[index as usize]);
+    }
+
+    // Seeking after some points have already been read must build the index
+    // from clean state rather than on top of the leftover decode buffers.
+    let mut raw = reader.pointcloud_raw(&pc).unwrap();
+    for _ in 0..500 {
+        raw.next().unwrap().unwrap();
+    }
+    for index in [0_u64, 777, 20000, pc.records - 1] {
+        raw.seek_record(index).unwrap();
+        let point = raw.next().unwrap().unwrap();
+        assert_eq!(point, all[index as usize]);
+    }
+}
+
+#[test]
+fn ignored_and_index_packets() {
+    // This file interleaves an Ignored packet and carries an Index packet in
+    // its CompressedVector section; reading it used to bail out or desync the
+    // packet stream. The reader must consume both transparently and still
+    // return every point.
+    let file = "testdata/bunnyIgnored.e57";
+    let mut reader = E57Reader::from_file(file).unwrap();
+    let pcs = reader.pointclouds();
+    let pc = pcs.first().unwrap();
+    let points: Vec<RawValues> = reader
+        .pointcloud_raw(pc)
+        .unwrap()
+        .map(|p| p.unwrap())
+        .collect();
+    assert_eq!(points.len() as u64, pc.records);
+}
+
 #[test]
 fn simple_iterator() {
     let file = "testdata/tinyCartesianFloatRgb.e57";